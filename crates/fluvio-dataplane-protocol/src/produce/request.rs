@@ -25,6 +25,15 @@ where
     #[fluvio(min_version = 3)]
     pub transactional_id: Option<String>,
 
+    /// The producer id, or -1 if the producer does not have an idempotent session.
+    #[fluvio(min_version = 3, default = "-1")]
+    pub producer_id: i64,
+
+    /// The producer epoch associated with `producer_id`, or -1 if the producer does not have
+    /// an idempotent session. Used to fence zombie producers.
+    #[fluvio(min_version = 3, default = "-1")]
+    pub producer_epoch: i16,
+
     /// The number of acknowledgments the producer requires the leader to have received before
     /// considering a request complete. Allowed values: 0 for no acknowledgments, 1 for only the
     /// leader and -1 for the full ISR.
@@ -42,10 +51,14 @@ impl<R> ProduceRequest<R>
 where
     R: Encoder + Decoder + Default + Debug,
 {
-    /// Get isolation from `acks`. Possible `acks` values: -1, 0, 1.
-    /// -1 is mapped to `ReadCommitted`.
-    /// 0, 1 are mapped to `ReadUncommitted`.
+    /// Get isolation from `transactional_id`/`acks`.
+    /// A set `transactional_id` always implies `ReadCommitted`.
+    /// Otherwise, -1 is mapped to `ReadCommitted`, 0 and 1 are mapped to `ReadUncommitted`.
     pub fn isolation(&self) -> Isolation {
+        if self.transactional_id.is_some() {
+            return Isolation::ReadCommitted;
+        }
+
         match self.acks {
             acks if acks < 0 => Isolation::ReadCommitted,
             _ => Isolation::ReadUncommitted,
@@ -60,6 +73,10 @@ where
     const API_KEY: u16 = 0;
 
     const MIN_API_VERSION: i16 = 0;
+    // Pinned below the KIP-482 flexible-version threshold (v8+): those versions need
+    // compact array/string framing and a tagged-fields section threaded through the core
+    // Encoder/Decoder and derive crates, which this crate does not implement. Raise this
+    // only alongside that codec work, not on its own.
     const MAX_API_VERSION: i16 = 7;
     const DEFAULT_API_VERSION: i16 = 7;
 
@@ -87,8 +104,113 @@ where
     /// The partition index.
     pub partition_index: i32,
 
-    /// The record data to be produced.
+    /// The sequence number assigned to the first record in this batch by an idempotent
+    /// producer, used together with `producer_id`/`producer_epoch` to detect duplicate
+    /// retries of the same batch.
+    #[fluvio(min_version = 3)]
+    pub base_sequence: i32,
+
+    /// The sequence number assigned to the first record in this batch. Equal to
+    /// `base_sequence` unless the batch has been split.
+    #[fluvio(min_version = 3)]
+    pub first_sequence: i32,
+
+    /// The sequence number assigned to the last record in this batch.
+    #[fluvio(min_version = 3)]
+    pub last_sequence: i32,
+
+    /// A hint brokers may use to order or preempt this batch's append relative to other
+    /// batches under load. Higher values are served first.
+    #[fluvio(min_version = 4)]
+    pub priority: i32,
+
+    /// The millisecond deadline after which this batch may be dropped if it has not yet
+    /// been acknowledged or consumed, or `None` if it never expires.
+    #[fluvio(min_version = 4)]
+    pub expires: Option<i64>,
+
+    /// The record data to be produced. Left at its default (empty) value when the partition
+    /// is produced via `fragments` instead; it is still always encoded, since it is a plain
+    /// required field on the wire and is not itself version-gated.
     pub records: R,
+
+    /// An ordered sequence of fragments carrying this partition's records instead of one
+    /// monolithic `records` batch, so a large payload can be flushed incrementally as chunks
+    /// arrive rather than buffered in full. Empty when the partition is produced via
+    /// `records` directly.
+    #[fluvio(min_version = 5)]
+    pub fragments: Vec<ProduceFragment<R>>,
+}
+
+/// One fragment of a streamed partition produce. A fragment accumulates its record chunks via
+/// [`PartitionProduceData::write_chunk`] and is closed with a known `size` once complete.
+/// Unknown size is allowed for all but the final fragment in the set, which must close the
+/// set with a known `size`.
+#[derive(Encoder, Decoder, FluvioDefault, Debug)]
+pub struct ProduceFragment<R>
+where
+    R: Encoder + Decoder + Default + Debug,
+{
+    /// The order of this fragment within the fragment set.
+    pub sequence: i32,
+
+    /// The size of this fragment in bytes once closed, or `None` while still streaming.
+    /// Only the final fragment in the set is required to carry a known size; earlier
+    /// fragments may leave it unset.
+    pub size: Option<i32>,
+
+    /// The chunks of record data written to this fragment, in arrival order.
+    pub chunks: Vec<R>,
+}
+
+impl<R> PartitionProduceData<R>
+where
+    R: Encoder + Decoder + Default + Debug,
+{
+    /// Start a new fragment at `sequence`, appending it to `fragments` and leaving it open
+    /// (`size == None`) until [`close_fragment`](Self::close_fragment) is called.
+    pub fn create_fragment(&mut self, sequence: i32) -> &mut Self {
+        self.fragments.push(ProduceFragment {
+            sequence,
+            size: None,
+            chunks: Vec::new(),
+        });
+        self
+    }
+
+    /// Append `chunk` to the most recently created fragment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no fragment has been created yet, or if the most recent fragment has
+    /// already been closed via [`close_fragment`](Self::close_fragment).
+    pub fn write_chunk(&mut self, chunk: R) -> &mut Self {
+        let fragment = self
+            .fragments
+            .last_mut()
+            .expect("write_chunk called before create_fragment");
+        assert!(
+            fragment.size.is_none(),
+            "write_chunk called on a fragment that has already been closed"
+        );
+        fragment.chunks.push(chunk);
+        self
+    }
+
+    /// Close the most recently created fragment with its total `size`, signalling that no
+    /// further chunks will be written to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no fragment has been created yet.
+    pub fn close_fragment(&mut self, size: i32) -> &mut Self {
+        let fragment = self
+            .fragments
+            .last_mut()
+            .expect("close_fragment called before create_fragment");
+        fragment.size = Some(size);
+        self
+    }
 }
 
 #[cfg(feature = "file")]
@@ -97,6 +219,7 @@ pub use file::*;
 #[cfg(feature = "file")]
 mod file {
     use std::io::Error as IoError;
+    use std::io::ErrorKind;
 
     use tracing::trace;
     use bytes::BytesMut;
@@ -111,6 +234,7 @@ mod file {
     pub type FileProduceRequest = ProduceRequest<FileRecordSet>;
     pub type FileTopicRequest = TopicProduceData<FileRecordSet>;
     pub type FilePartitionRequest = PartitionProduceData<FileRecordSet>;
+    pub type FileProduceFragment = ProduceFragment<FileRecordSet>;
 
     impl FileWrite for FileProduceRequest {
         fn file_encode(
@@ -121,6 +245,10 @@ mod file {
         ) -> Result<(), IoError> {
             trace!("file encoding produce request");
             self.transactional_id.encode(src, version)?;
+            if version >= 3 {
+                self.producer_id.encode(src, version)?;
+                self.producer_epoch.encode(src, version)?;
+            }
             self.acks.encode(src, version)?;
             self.timeout_ms.encode(src, version)?;
             self.topics.file_encode(src, data, version)?;
@@ -151,8 +279,167 @@ mod file {
         ) -> Result<(), IoError> {
             trace!("file encoding for partition request");
             self.partition_index.encode(src, version)?;
+            if version >= 3 {
+                self.base_sequence.encode(src, version)?;
+                self.first_sequence.encode(src, version)?;
+                self.last_sequence.encode(src, version)?;
+            }
+            // Must mirror the derived Encoder's min_version gate, or the file path and the
+            // derive-based path disagree on how many bytes a given version carries.
+            if version >= 4 {
+                self.priority.encode(src, version)?;
+                self.expires.encode(src, version)?;
+            }
             self.records.file_encode(src, data, version)?;
+            if version >= 5 {
+                validate_fragments(&self.fragments, self.partition_index)?;
+                self.fragments.file_encode(src, data, version)?;
+            }
             Ok(())
         }
     }
+
+    impl FileWrite for FileProduceFragment {
+        fn file_encode(
+            &self,
+            src: &mut BytesMut,
+            data: &mut Vec<StoreValue>,
+            version: Version,
+        ) -> Result<(), IoError> {
+            trace!("file encoding produce fragment header");
+            self.sequence.encode(src, version)?;
+            self.size.encode(src, version)?;
+            self.chunks.file_encode(src, data, version)?;
+            Ok(())
+        }
+    }
+
+    /// Enforce that the final fragment in a set carries a known size, closing it. Earlier
+    /// fragments are allowed to leave their size unknown.
+    fn validate_fragments<R>(
+        fragments: &[ProduceFragment<R>],
+        partition_index: i32,
+    ) -> Result<(), IoError>
+    where
+        R: Encoder + Decoder + Default + Debug,
+    {
+        if let Some(last) = fragments.last() {
+            if last.size.is_none() {
+                return Err(IoError::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "partition {partition_index} fragment {} must carry a known size to close the fragment set",
+                        last.sequence
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        fn fragment(sequence: i32, size: Option<i32>) -> FileProduceFragment {
+            ProduceFragment {
+                sequence,
+                size,
+                chunks: Vec::new(),
+            }
+        }
+
+        #[test]
+        fn test_validate_fragments_allows_unknown_size_before_the_last_fragment() {
+            let fragments = vec![fragment(0, None), fragment(1, None), fragment(2, Some(100))];
+            assert!(validate_fragments(&fragments, 0).is_ok());
+        }
+
+        #[test]
+        fn test_validate_fragments_requires_the_last_fragment_to_be_closed() {
+            let fragments = vec![fragment(0, Some(10)), fragment(1, None)];
+            assert!(validate_fragments(&fragments, 0).is_err());
+        }
+
+        #[test]
+        fn test_file_encode_rejects_partition_with_unclosed_final_fragment() {
+            let mut partition = FilePartitionRequest::default();
+            partition.create_fragment(0);
+            partition.write_chunk(FileRecordSet::default());
+
+            let mut src = BytesMut::new();
+            let mut data = Vec::new();
+            assert!(partition.file_encode(&mut src, &mut data, 5).is_err());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_isolation_with_transactional_id() {
+        let request = DefaultProduceRequest {
+            transactional_id: Some("tx-1".to_owned()),
+            acks: 1,
+            ..Default::default()
+        };
+        assert_eq!(request.isolation(), Isolation::ReadCommitted);
+    }
+
+    #[test]
+    fn test_isolation_without_transactional_id() {
+        let committed = DefaultProduceRequest {
+            acks: -1,
+            ..Default::default()
+        };
+        assert_eq!(committed.isolation(), Isolation::ReadCommitted);
+
+        let uncommitted = DefaultProduceRequest {
+            acks: 1,
+            ..Default::default()
+        };
+        assert_eq!(uncommitted.isolation(), Isolation::ReadUncommitted);
+    }
+
+    #[test]
+    fn test_write_chunk_accumulates_into_current_fragment() {
+        let mut partition = DefaultPartitionRequest::default();
+        partition.create_fragment(0);
+        partition.write_chunk(RecordSet::default());
+        partition.write_chunk(RecordSet::default());
+
+        assert_eq!(partition.fragments.len(), 1);
+        assert_eq!(partition.fragments[0].chunks.len(), 2);
+    }
+
+    #[test]
+    fn test_close_fragment_sets_size_and_next_created_fragment_is_open() {
+        let mut partition = DefaultPartitionRequest::default();
+        partition.create_fragment(0);
+        partition.write_chunk(RecordSet::default());
+        partition.close_fragment(42);
+        partition.create_fragment(1);
+        partition.write_chunk(RecordSet::default());
+
+        assert_eq!(partition.fragments[0].size, Some(42));
+        assert_eq!(partition.fragments[1].size, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "write_chunk called before create_fragment")]
+    fn test_write_chunk_without_fragment_panics() {
+        let mut partition = DefaultPartitionRequest::default();
+        partition.write_chunk(RecordSet::default());
+    }
+
+    #[test]
+    #[should_panic(expected = "already been closed")]
+    fn test_write_chunk_after_close_panics() {
+        let mut partition = DefaultPartitionRequest::default();
+        partition.create_fragment(0);
+        partition.close_fragment(0);
+        partition.write_chunk(RecordSet::default());
+    }
 }